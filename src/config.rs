@@ -0,0 +1,103 @@
+use crate::read_lines;
+use dirs::config_dir;
+use std::path::PathBuf;
+
+/// User configuration read from `~/.config/jumper/config.toml`.
+///
+/// Two sections are understood:
+///
+/// ```toml
+/// [rewrites]
+/// "/run/media/fib/ExternalSSD/code" = "code"
+///
+/// [aliases]
+/// dotfiles = "/home/fib/.config"
+/// ```
+///
+/// `rewrites` is an ordered list of `prefix = replacement` rules matched as
+/// plain string prefixes of a path (the first match wins); `aliases` maps a friendly display name onto the
+/// project path it should stand in for.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Ordered `prefix -> replacement` rewrite rules.
+    pub rewrites: Vec<(String, String)>,
+    /// `name -> path` aliases keyed for reverse lookup by path.
+    pub aliases: Vec<(String, String)>,
+}
+
+impl Config {
+    /// Loads the config from `~/.config/jumper/config.toml`, returning the
+    /// default (empty) config when the file is missing or unreadable.
+    #[must_use]
+    pub fn load() -> Self {
+        let Some(path) = config_path() else {
+            return Self::default();
+        };
+        let Ok(lines) = read_lines(&path) else {
+            return Self::default();
+        };
+        Self::parse(&lines)
+    }
+
+    fn parse(lines: &[String]) -> Self {
+        let mut config = Self::default();
+        let mut section = String::new();
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = unquote(key.trim());
+            let value = unquote(value.trim());
+            match section.as_str() {
+                "rewrites" => config.rewrites.push((key, value)),
+                "aliases" => config.aliases.push((key, value)),
+                _ => {}
+            }
+        }
+        config
+    }
+
+    /// Returns the alias name configured for `path`, if any.
+    #[must_use]
+    pub fn alias_for(&self, path: &str) -> Option<&str> {
+        self.aliases
+            .iter()
+            .find(|(_, target)| target == path)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Applies the ordered rewrite rules to `path`, returning the first match.
+    #[must_use]
+    pub fn rewrite(&self, path: &str) -> String {
+        for (prefix, replacement) in &self.rewrites {
+            if let Some(rest) = path.strip_prefix(prefix) {
+                return format!("{replacement}{rest}");
+            }
+        }
+        path.to_string()
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("jumper").join("config.toml"))
+}
+
+/// Strips a single matching pair of surrounding quotes from a TOML value.
+fn unquote(value: &str) -> String {
+    let bytes = value.as_bytes();
+    if value.len() >= 2
+        && (bytes[0] == b'"' || bytes[0] == b'\'')
+        && bytes[bytes.len() - 1] == bytes[0]
+    {
+        return value[1..value.len() - 1].to_string();
+    }
+    value.to_string()
+}
@@ -1,5 +1,10 @@
+pub mod config;
+pub mod sources;
 pub mod tmux;
 
+use crate::config::Config;
+use crate::sources::{ProjectsFile, SessionSource, SshKnownHosts, TmuxSessions};
+
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
 use dirs::home_dir;
@@ -17,6 +22,20 @@ extern crate whoami;
 struct Opt {
     #[command(subcommand)]
     command: Option<Commands>,
+    /// Attach read-only, ignoring input to the selected session
+    #[arg(short = 'r', long = "readonly", global = true)]
+    readonly: bool,
+    /// Force-detach other clients when attaching to the selected session
+    #[arg(short = 'd', long = "detach", global = true)]
+    detach: bool,
+    /// Jump directly to this window inside the selected session
+    window: Option<String>,
+    /// Operate on an isolated tmux server using this socket name (`tmux -L`)
+    #[arg(short = 'L', long = "socket", global = true)]
+    socket: Option<String>,
+    /// Hide sessions already attached by another client from the candidate list
+    #[arg(long = "exclude-attached", global = true)]
+    exclude_attached: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -42,6 +61,9 @@ enum Commands {
     /// Clear the cache file
     #[command(name = "clear-cache", aliases = &["cc"])]
     ClearCache,
+    /// Kill one or more running tmux sessions
+    #[command(name = "kill", aliases = &["k", "stop"])]
+    Kill,
     /// Generate shell completion scripts
     #[command(name = "completion", aliases = &["comp", "c"])]
     Completion {
@@ -52,37 +74,111 @@ enum Commands {
 }
 
 #[derive(Debug, Clone)]
-struct Project {
+pub struct Project {
     path: String,
+    /// Explicit display name (from a `name = path` entry in `.projects`).
+    /// When set it is shown instead of the rewritten path (dots are still
+    /// stripped, as for every fzf label).
+    name: Option<String>,
+    /// Initial command to run in the new session instead of a plain shell
+    /// (e.g. `ssh <host>` for a remote target). `None` opens a shell.
+    command: Option<String>,
 }
 
 impl Project {
     fn new(path: &str) -> Self {
         Self {
             path: path.to_string(),
+            name: None,
+            command: None,
+        }
+    }
+
+    /// Builds a project shown under a friendly `name` rather than its path.
+    fn named(name: &str, path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            name: Some(name.to_string()),
+            command: None,
         }
     }
 
-    fn to_fzf_display(&self) -> String {
-        let user = whoami::username();
-        self.path
-            .replace(&format!("/home/{user}"), "~")
-            .replace("/run/media/fib/ExternalSSD/code", "code")
-            .replace('.', "")
+    /// Builds a remote target that opens an `ssh <host>` session.
+    fn ssh(host: &str) -> Self {
+        Self {
+            path: host.to_string(),
+            name: None,
+            command: Some(format!("ssh {host}")),
+        }
+    }
+
+    /// Renders the label shown in fzf. An explicit name (or configured alias)
+    /// wins; otherwise the configured rewrite rules and the home shorthand are
+    /// applied to the path. Dots are stripped because tmux treats them
+    /// specially in session names.
+    fn to_fzf_display(&self, config: &Config) -> String {
+        let label = if let Some(name) = &self.name {
+            name.clone()
+        } else if let Some(alias) = config.alias_for(&self.path) {
+            alias.to_string()
+        } else {
+            let user = whoami::username();
+            config
+                .rewrite(&self.path)
+                .replace(&format!("/home/{user}"), "~")
+        };
+        label.replace('.', "")
+    }
+
+    /// Encodes the project as a single tab-separated cache line so the cache
+    /// round-trips fields a bare path cannot carry (the friendly `name` and
+    /// the `ssh <host>` command). Missing fields are left as empty columns.
+    fn to_cache_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}",
+            self.path,
+            self.name.as_deref().unwrap_or(""),
+            self.command.as_deref().unwrap_or(""),
+        )
+    }
+
+    /// Reverses `to_cache_line`. Lines without tabs are treated as a bare
+    /// path so pre-existing caches still load.
+    fn from_cache_line(line: &str) -> Self {
+        let mut parts = line.split('\t');
+        let path = parts.next().unwrap_or("").to_string();
+        let name = parts.next().filter(|s| !s.is_empty()).map(str::to_string);
+        let command = parts.next().filter(|s| !s.is_empty()).map(str::to_string);
+        Self {
+            path,
+            name,
+            command,
+        }
     }
 }
 
 fn main() {
     let opt = Opt::parse();
+    let socket = opt.socket.clone();
+    let exclude_attached = opt.exclude_attached;
     match opt.command {
         Some(Commands::Add { dir }) => add_project(dir.as_deref()),
         Some(Commands::Delete) => delete_project(),
-        Some(Commands::List) => list_projects(),
+        Some(Commands::List) => list_projects(socket.as_deref()),
         Some(Commands::Status) => status_projects(),
         Some(Commands::SetDepth) => set_depth(),
         Some(Commands::ClearCache) => clear_cache(),
+        Some(Commands::Kill) => kill_sessions(socket.as_deref(), exclude_attached),
         Some(Commands::Completion { shell }) => generate_completion(shell),
-        None => main_execution(),
+        None => main_execution(
+            &tmux::AttachOptions {
+                readonly: opt.readonly,
+                detach_others: opt.detach,
+                window: opt.window,
+            },
+            socket.as_deref(),
+            exclude_attached,
+        ),
     }
 }
 
@@ -92,7 +188,7 @@ fn generate_completion(shell: Shell) {
     generate(shell, &mut cmd, bin_name, &mut std::io::stdout());
 }
 
-fn get_home_path(file: &str) -> PathBuf {
+pub fn get_home_path(file: &str) -> PathBuf {
     home_dir()
         .expect("Unable to find home directory")
         .join(file)
@@ -106,7 +202,7 @@ fn touch_file(path: &PathBuf) {
         .unwrap();
 }
 
-fn read_lines<P>(filename: P) -> std::io::Result<Vec<String>>
+pub fn read_lines<P>(filename: P) -> std::io::Result<Vec<String>>
 where
     P: AsRef<std::path::Path>,
 {
@@ -168,96 +264,42 @@ fn delete_project() {
     }
 }
 
-fn get_tmux_sessions() -> Vec<Project> {
-    let mut projects = Vec::new();
-    let tmux_list_output = Command::new("tmux")
-        .arg("list-sessions")
-        .arg("-F")
-        .arg("#{session_name}")
-        .output()
-        .expect("Failed to list tmux sessions");
-    dbg!(&tmux_list_output);
-    if tmux_list_output.status.success() {
-        let tmux_sessions = String::from_utf8_lossy(&tmux_list_output.stdout);
-        for session in tmux_sessions.lines() {
-            if let Some(session_name) = session.split(':').next() {
-                projects.push(Project::new(session_name));
-            }
-        }
-    }
-    projects
-}
-
-fn get_projects() -> Vec<Project> {
-    let projects_file = get_home_path(".projects");
-    let mut projects = Vec::new();
-    let mut unique_projects = HashSet::new();
-
-    if let Ok(lines) = read_lines(&projects_file) {
-        let re = Regex::new(r"(.*) --depth (\d+)").unwrap();
-        for line in lines {
-            if let Some(captures) = re.captures(&line) {
-                let dir = captures.get(1).unwrap().as_str();
-                let depth = captures.get(2).unwrap().as_str().parse::<u32>().unwrap();
-                projects.push(Project::new(dir));
-                let sub_dirs = Command::new("find")
-                    .arg("-L")
-                    .arg(dir)
-                    .arg("-maxdepth")
-                    .arg(depth.to_string())
-                    .arg("-type")
-                    .arg("d")
-                    .output()
-                    .expect("Failed to execute find");
-                let sub_dirs = String::from_utf8_lossy(&sub_dirs.stdout);
-                for sub_dir in sub_dirs.lines() {
-                    projects.push(Project::new(sub_dir));
-                }
-            } else {
-                projects.push(Project::new(&line));
-            }
-        }
-    }
-
-    projects.extend(get_tmux_sessions());
-
-    projects
-        .into_iter()
-        .filter(|project| unique_projects.insert(project.path.clone()))
-        .collect()
-}
-
-fn reorder_projects_by_history(history: &[String], projects: &[Project]) -> Vec<Project> {
-    let mut reordered_projects = Vec::new();
-    let mut seen = HashSet::new();
-    let projects_map: HashMap<String, &Project> =
-        projects.iter().map(|p| (p.to_fzf_display(), p)).collect();
-    for hist in history {
-        if let Some(project) = projects_map.get(hist) {
-            if seen.insert(project.path.clone()) {
-                reordered_projects.push((*project).clone());
-            }
-        }
-    }
-    for project in projects {
-        if seen.insert(project.path.clone()) {
-            reordered_projects.push(project.clone());
-        }
-    }
-    reordered_projects
+fn get_projects(socket: Option<&str>) -> Vec<Project> {
+    let sources: Vec<Box<dyn SessionSource>> = vec![
+        Box::new(ProjectsFile),
+        Box::new(TmuxSessions {
+            socket: socket.map(str::to_string),
+        }),
+        Box::new(SshKnownHosts),
+    ];
+    sources::merge(&sources)
 }
 
-fn move_to_tmux_session(dir: &Project) {
-    let tmux_session_name_og = dir.to_fzf_display();
+fn move_to_tmux_session(
+    dir: &Project,
+    config: &Config,
+    options: &tmux::AttachOptions,
+    socket: Option<&str>,
+) {
+    let tmux_session_name_og = dir.to_fzf_display(config);
     let tmux_session_name = tmux_session_name_og.replace('~', "\\~");
 
     // Check if the session already exists
-    let tmux_session_already_exists = tmux::session_exists(&tmux_session_name_og);
+    let tmux_session_already_exists = tmux::session_exists(&tmux_session_name_og, socket);
 
-    // Create a new tmux session if it doesn't exist
-    if !tmux_session_already_exists && !tmux::create_session(&tmux_session_name_og, &dir.path) {
-        eprintln!("Failed to create new tmux session");
-        return;
+    // Create a new tmux session if it doesn't exist. Remote (SSH) targets open
+    // with `ssh <host>` as their initial command from the user's home directory
+    // rather than a plain shell in a local project directory.
+    if !tmux_session_already_exists {
+        let cwd = if dir.command.is_some() {
+            sources::command_cwd()
+        } else {
+            dir.path.clone()
+        };
+        if !tmux::create_session(&tmux_session_name_og, &cwd, dir.command.as_deref(), socket) {
+            eprintln!("Failed to create new tmux session");
+            return;
+        }
     }
 
     // Determine if running inside a tmux session
@@ -265,37 +307,19 @@ fn move_to_tmux_session(dir: &Project) {
 
     if is_inside_tmux {
         // Running inside tmux: switch client to the session
-        if !tmux::switch_client(&tmux_session_name) {
+        if !tmux::switch_client(&tmux_session_name, options, socket) {
             eprintln!("Failed to switch tmux client");
         }
     } else {
         // Running outside tmux: attach to the session
-        if !tmux::attach_session(&tmux_session_name) {
+        if !tmux::attach_session(&tmux_session_name, options, socket) {
             eprintln!("Failed to attach to tmux session");
         }
     }
 }
 
-fn get_current_session() -> Option<String> {
-    let output = Command::new("tmux")
-        .arg("display-message")
-        .arg("-p")
-        .arg("#S")
-        .output()
-        .expect("Failed to execute tmux command");
-    if output.status.success() {
-        let session_name = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Some(session_name)
-    } else {
-        eprintln!("Failed to get current tmux session name");
-        None
-    }
-}
-
-fn main_execution() {
-    let projects_history_file = get_home_path(".projects_history");
-    touch_file(&projects_history_file);
-    let history_lines = read_lines(&projects_history_file).unwrap_or_else(|_| vec![]);
+fn main_execution(options: &tmux::AttachOptions, socket: Option<&str>, exclude_attached: bool) {
+    let config = Config::load();
     let projects_file = get_home_path(".projects");
     let cache_file = PathBuf::from("/tmp/.projects_cache");
     let projects_metadata =
@@ -313,41 +337,67 @@ fn main_execution() {
     {
         read_lines(&cache_file)
             .unwrap()
-            .into_iter()
-            .map(|line| Project::new(&line))
+            .iter()
+            .map(|line| Project::from_cache_line(line))
             .collect()
     } else {
-        let new_projects = get_projects();
-        let project_paths: Vec<String> = new_projects.iter().map(|p| p.path.clone()).collect();
-        write_lines(&cache_file, &project_paths).unwrap();
+        let new_projects = get_projects(socket);
+        let cache_lines: Vec<String> = new_projects.iter().map(Project::to_cache_line).collect();
+        write_lines(&cache_file, &cache_lines).unwrap();
         new_projects
     };
-    let reordered_projects = reorder_projects_by_history(&history_lines, &projects);
-    let current_session = get_current_session();
-    let project_set: HashSet<_> = projects
-        .iter()
-        .filter_map(|p| {
-            if let Some(current_session) = &current_session {
-                if Project::new(&p.path).to_fzf_display() == *current_session {
-                    return None;
+    let current_session = tmux::get_current_session(socket);
+
+    // Build a recency map from live tmux sessions. On a name collision, keep
+    // the entry with the larger timestamp (last_attached taking precedence).
+    // With `--exclude-attached`, sessions already in use elsewhere are dropped
+    // so they are never offered as switch candidates.
+    let mut sessions: HashMap<String, tmux::Session> = HashMap::new();
+    let mut attached: HashSet<String> = HashSet::new();
+    for session in tmux::get_sessions(socket) {
+        if exclude_attached && session.attached {
+            attached.insert(session.name);
+            continue;
+        }
+        sessions
+            .entry(session.name.clone())
+            .and_modify(|existing| {
+                if session.recency() > existing.recency() {
+                    *existing = session.clone();
                 }
-            }
-            Some(p.to_fzf_display())
-        })
-        .collect();
-    let mut fzf_through: Vec<String> =
-        Vec::with_capacity(history_lines.len() + reordered_projects.len());
+            })
+            .or_insert(session);
+    }
+
+    // Sort descending by the session's max(last_attached, created) so the
+    // most-recently-used sessions float to the top, then append projects with
+    // no live session (recency 0) in discovery order.
+    let mut live: Vec<(u64, String)> = Vec::new();
+    let mut dormant: Vec<String> = Vec::new();
     let mut seen = HashSet::new();
-    for item in &history_lines {
-        if project_set.contains(item) && seen.insert(item.clone()) {
-            fzf_through.push(item.clone());
+    for project in &projects {
+        let display = project.to_fzf_display(&config);
+        if let Some(current_session) = &current_session {
+            if display == *current_session {
+                continue;
+            }
         }
-    }
-    for project in &reordered_projects {
-        if seen.insert(project.to_fzf_display()) {
-            fzf_through.push(project.to_fzf_display());
+        if exclude_attached && attached.contains(&display) {
+            continue;
+        }
+        if !seen.insert(display.clone()) {
+            continue;
+        }
+        match sessions.get(&display) {
+            Some(session) => live.push((session.recency(), display)),
+            None => dormant.push(display),
         }
     }
+    live.sort_by_key(|b| std::cmp::Reverse(b.0));
+    let mut fzf_through: Vec<String> = Vec::with_capacity(live.len() + dormant.len());
+    fzf_through.extend(live.into_iter().map(|(_, display)| display));
+    fzf_through.extend(dormant);
+
     let mut selected = Command::new("fzf")
         .arg("--reverse")
         .stdin(Stdio::piped())
@@ -367,28 +417,18 @@ fn main_execution() {
     if selected_str.is_empty() {
         return;
     }
-    let mut new_history = vec![selected_str.clone()];
-    new_history.extend(
-        history_lines
-            .iter()
-            .filter(|&item| item != &selected_str)
-            .cloned(),
-    );
-    new_history.truncate(2000);
-    write_lines(&projects_history_file, &new_history).unwrap();
-    if let Some(idx) = reordered_projects
+    if let Some(dir) = projects
         .iter()
-        .position(|p| p.to_fzf_display() == selected_str)
+        .find(|p| p.to_fzf_display(&config) == selected_str)
     {
-        let dir = reordered_projects.get(idx).unwrap();
-        move_to_tmux_session(dir);
+        move_to_tmux_session(dir, &config, options, socket);
     } else {
         println!("L");
     }
 }
 
-fn list_projects() {
-    let projects = get_projects();
+fn list_projects(socket: Option<&str>) {
+    let projects = get_projects(socket);
     for project in projects {
         println!("{}", project.path);
     }
@@ -446,6 +486,55 @@ fn set_depth() {
     }
 }
 
+fn kill_sessions(socket: Option<&str>, exclude_attached: bool) {
+    let names: Vec<String> = tmux::get_sessions(socket)
+        .into_iter()
+        .filter(|session| !(exclude_attached && session.attached))
+        .map(|session| session.name)
+        .collect();
+    if names.is_empty() {
+        println!("No tmux sessions to kill");
+        return;
+    }
+    let mut selected = Command::new("fzf")
+        .arg("--reverse")
+        .arg("--multi")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute fzf");
+    {
+        let fzf_stdin = selected.stdin.as_mut().expect("Failed to open fzf stdin");
+        fzf_stdin
+            .write_all(names.join("\n").as_bytes())
+            .expect("Failed to write to fzf stdin");
+    }
+    let output = selected
+        .wait_with_output()
+        .expect("Failed to read fzf output");
+    if output.stdout.is_empty() {
+        return;
+    }
+    let current_session = tmux::get_current_session(socket);
+    for name in String::from_utf8_lossy(&output.stdout).lines() {
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        // Killing the attached session from within would detach the client, so
+        // skip it and leave the current session alone.
+        if current_session.as_deref() == Some(name) {
+            eprintln!("Skipping current session \"{name}\"");
+            continue;
+        }
+        if tmux::kill_session(name, socket) {
+            println!("Killed \"{name}\"");
+        } else {
+            eprintln!("Failed to kill \"{name}\"");
+        }
+    }
+}
+
 fn clear_cache() {
     let cache_file = PathBuf::from("/tmp/.projects_cache");
     if cache_file.exists() {
@@ -0,0 +1,188 @@
+use crate::{get_home_path, read_lines, Project};
+use dirs::home_dir;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::process::Command;
+
+/// Errors produced while collecting sessions from a [`SessionSource`].
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// A source of jumpable targets.
+///
+/// Each implementor knows how to enumerate its own kind of target (local
+/// projects, live tmux sessions, remote SSH hosts, ...). [`merge`] folds the
+/// results of several sources into a single deduplicated list.
+pub trait SessionSource {
+    /// Enumerates the targets exposed by this source.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying files or commands cannot be read.
+    fn sessions(&self) -> Result<Vec<Project>, Error>;
+}
+
+/// Reads projects from the `~/.projects` file, expanding `--depth N` entries
+/// into their matching subdirectories via `find`.
+pub struct ProjectsFile;
+
+impl SessionSource for ProjectsFile {
+    fn sessions(&self) -> Result<Vec<Project>, Error> {
+        let projects_file = get_home_path(".projects");
+        let mut projects = Vec::new();
+
+        let Ok(lines) = read_lines(&projects_file) else {
+            return Ok(projects);
+        };
+        let re = Regex::new(r"(.*) --depth (\d+)").unwrap();
+        for line in lines {
+            // `name = path` entries are shown under a friendly label.
+            if let Some((name, path)) = line.split_once('=') {
+                if !line.contains("--depth") {
+                    projects.push(Project::named(name.trim(), path.trim()));
+                    continue;
+                }
+            }
+            if let Some(captures) = re.captures(&line) {
+                let dir = captures.get(1).unwrap().as_str();
+                let depth = captures.get(2).unwrap().as_str().parse::<u32>().unwrap();
+                projects.push(Project::new(dir));
+                let sub_dirs = Command::new("find")
+                    .arg("-L")
+                    .arg(dir)
+                    .arg("-maxdepth")
+                    .arg(depth.to_string())
+                    .arg("-type")
+                    .arg("d")
+                    .output()
+                    .expect("Failed to execute find");
+                let sub_dirs = String::from_utf8_lossy(&sub_dirs.stdout);
+                for sub_dir in sub_dirs.lines() {
+                    projects.push(Project::new(sub_dir));
+                }
+            } else {
+                projects.push(Project::new(&line));
+            }
+        }
+
+        Ok(projects)
+    }
+}
+
+/// Lists the currently running tmux sessions on the configured socket.
+pub struct TmuxSessions {
+    pub socket: Option<String>,
+}
+
+impl SessionSource for TmuxSessions {
+    fn sessions(&self) -> Result<Vec<Project>, Error> {
+        Ok(crate::tmux::get_sessions(self.socket.as_deref())
+            .into_iter()
+            .map(|session| Project::new(&session.name))
+            .collect())
+    }
+}
+
+/// Parses `~/.ssh/known_hosts` and the `Host` entries in `~/.ssh/config` into
+/// selectable remote targets. Picking one of these opens a tmux session whose
+/// initial command is `ssh <host>`.
+pub struct SshKnownHosts;
+
+impl SessionSource for SshKnownHosts {
+    fn sessions(&self) -> Result<Vec<Project>, Error> {
+        let mut hosts = HashSet::new();
+
+        if let Ok(lines) = read_lines(get_home_path(".ssh/known_hosts")) {
+            for line in lines {
+                // Skip hashed entries (`|1|...`), markers (`@revoked`) and blanks.
+                let Some(field) = line.split_whitespace().next() else {
+                    continue;
+                };
+                if field.starts_with('|') || field.starts_with('@') {
+                    continue;
+                }
+                for host in field.split(',') {
+                    // Drop the `[host]:port` bracket syntax down to the bare host.
+                    let host = host.trim_start_matches('[');
+                    let host = host.split(']').next().unwrap_or(host);
+                    if !host.is_empty() {
+                        hosts.insert(host.to_string());
+                    }
+                }
+            }
+        }
+
+        if let Ok(lines) = read_lines(get_home_path(".ssh/config")) {
+            for line in lines {
+                let line = line.trim();
+                if let Some(rest) = line.strip_prefix("Host ") {
+                    for host in rest.split_whitespace() {
+                        // Pattern entries aren't connectable targets.
+                        if host.contains('*') || host.contains('?') {
+                            continue;
+                        }
+                        hosts.insert(host.to_string());
+                    }
+                }
+            }
+        }
+
+        let mut hosts: Vec<String> = hosts.into_iter().collect();
+        hosts.sort();
+        Ok(hosts
+            .into_iter()
+            .map(|host| Project::ssh(&host))
+            .collect())
+    }
+}
+
+/// Folds every source's targets into one deduplicated list, preserving the
+/// order in which the sources are listed. Sources that return an error are
+/// reported and skipped so they do not hide the others; a source that panics
+/// (for example when `tmux` or `find` cannot be run) still aborts the merge.
+#[must_use]
+pub fn merge(sources: &[Box<dyn SessionSource>]) -> Vec<Project> {
+    let mut merged = Vec::new();
+    let mut seen = HashSet::new();
+    for source in sources {
+        match source.sessions() {
+            Ok(projects) => {
+                for project in projects {
+                    if seen.insert(project.path.clone()) {
+                        merged.push(project);
+                    }
+                }
+            }
+            Err(err) => eprintln!("Failed to collect sessions from a source: {err}"),
+        }
+    }
+    merged
+}
+
+/// Uses the user's home directory as the working directory for sessions that
+/// launch a command (such as `ssh`) rather than opening a local project.
+#[must_use]
+pub fn command_cwd() -> String {
+    home_dir()
+        .expect("Unable to find home directory")
+        .to_string_lossy()
+        .into_owned()
+}
@@ -7,28 +7,73 @@ pub fn is_inside_tmux() -> bool {
     env::var("TMUX").is_ok()
 }
 
-/// Gets a list of all tmux sessions.
+/// Builds a `tmux` command bound to an optional `-L <socket>` server so jumper
+/// can operate on an isolated tmux server rather than the default socket.
+fn command(socket: Option<&str>) -> Command {
+    let mut cmd = Command::new("tmux");
+    if let Some(socket) = socket {
+        cmd.arg("-L").arg(socket);
+    }
+    cmd
+}
+
+/// A live tmux session together with its recency timestamps.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub name: String,
+    /// Epoch seconds of the last client attach, or `None` if never attached.
+    pub last_attached: Option<u64>,
+    /// Epoch seconds the session was created.
+    pub created: u64,
+    /// Whether a client is currently attached to this session.
+    pub attached: bool,
+}
+
+impl Session {
+    /// Recency used for ordering: the most recent of `last_attached` and
+    /// `created`, with `last_attached` taking precedence when present.
+    #[must_use]
+    pub fn recency(&self) -> u64 {
+        self.last_attached.unwrap_or(0).max(self.created)
+    }
+}
+
+/// Gets all live tmux sessions with their attach/create timestamps.
 ///
 /// # Panics
 /// Panics if the `tmux list-sessions` command fails to execute.
 #[must_use]
-pub fn get_sessions() -> Vec<String> {
-    let output = Command::new("tmux")
+pub fn get_sessions(socket: Option<&str>) -> Vec<Session> {
+    let output = command(socket)
         .arg("list-sessions")
         .arg("-F")
-        .arg("#{session_name}")
+        .arg("#{session_name}:#{session_last_attached}:#{session_created}:#{session_attached}")
         .output()
         .expect("Failed to list tmux sessions");
 
-    if output.status.success() {
-        let sessions = String::from_utf8_lossy(&output.stdout);
-        sessions
-            .lines()
-            .map(std::string::ToString::to_string)
-            .collect()
-    } else {
-        Vec::new()
+    if !output.status.success() {
+        return Vec::new();
     }
+
+    let sessions = String::from_utf8_lossy(&output.stdout);
+    sessions
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.rsplitn(4, ':');
+            // `session_attached` is a client count; non-zero means attached.
+            let attached = fields.next()?.parse::<u64>().ok()? != 0;
+            let created = fields.next()?.parse::<u64>().ok()?;
+            let last_attached = fields.next().and_then(|v| v.parse::<u64>().ok());
+            let name = fields.next()?.to_string();
+            Some(Session {
+                name,
+                // tmux reports 0 for sessions that have never been attached.
+                last_attached: last_attached.filter(|&v| v != 0),
+                created,
+                attached,
+            })
+        })
+        .collect()
 }
 
 /// Checks if a tmux session with the given name exists.
@@ -36,8 +81,8 @@ pub fn get_sessions() -> Vec<String> {
 /// # Panics
 /// Panics if the `tmux list-sessions` command fails to execute.
 #[must_use]
-pub fn session_exists(session_name: &str) -> bool {
-    let output = Command::new("tmux")
+pub fn session_exists(session_name: &str, socket: Option<&str>) -> bool {
+    let output = command(socket)
         .arg("list-sessions")
         .output()
         .expect("Failed to list tmux sessions");
@@ -50,52 +95,122 @@ pub fn session_exists(session_name: &str) -> bool {
 
 /// Creates a new tmux session with the given name in the specified directory.
 ///
+/// If `initial_command` is `Some`, it becomes the session's initial command (for
+/// example `ssh <host>`) instead of a plain login shell.
+///
 /// # Panics
 /// Panics if:
 /// - The directory change fails
 /// - The `tmux new-session` command fails to execute
 #[must_use]
-pub fn create_session(session_name: &str, dir: &str) -> bool {
+pub fn create_session(
+    session_name: &str,
+    dir: &str,
+    initial_command: Option<&str>,
+    socket: Option<&str>,
+) -> bool {
     env::set_current_dir(Path::new(dir))
         .unwrap_or_else(|_| panic!("Failed to change directory to {dir}"));
-    Command::new("tmux")
-        .arg("new-session")
-        .arg("-d")
-        .arg("-s")
-        .arg(session_name)
-        .status()
+    let mut cmd = command(socket);
+    cmd.arg("new-session").arg("-d").arg("-s").arg(session_name);
+    if let Some(initial_command) = initial_command {
+        cmd.arg(initial_command);
+    }
+    cmd.status()
         .expect("Failed to create new tmux session")
         .success()
 }
 
-/// Switches the current tmux client to the specified session.
+/// Options controlling how a session is attached to or switched to.
+#[derive(Debug, Clone, Default)]
+pub struct AttachOptions {
+    /// Attach read-only (`tmux attach -r`), ignoring input to the session.
+    pub readonly: bool,
+    /// Detach any other clients already attached (`tmux attach -d`).
+    pub detach_others: bool,
+    /// Jump directly to this window inside the target session.
+    pub window: Option<String>,
+}
+
+/// Selects the named window inside the given session.
 ///
 /// # Panics
-/// Panics if the `tmux switch-client` command fails to execute.
+/// Panics if the `tmux select-window` command fails to execute.
 #[must_use]
-pub fn switch_client(session_name: &str) -> bool {
-    Command::new("tmux")
-        .arg("switch-client")
+pub fn select_window(session_name: &str, window: &str, socket: Option<&str>) -> bool {
+    command(socket)
+        .arg("select-window")
         .arg("-t")
-        .arg(session_name)
+        .arg(format!("{session_name}:{window}"))
         .status()
+        .expect("Failed to select tmux window")
+        .success()
+}
+
+/// Switches the current tmux client to the specified session.
+///
+/// When [`AttachOptions::window`] is set the target window is selected first,
+/// and `readonly` is honoured via `switch-client -r`.
+///
+/// # Panics
+/// Panics if the `tmux switch-client` command fails to execute.
+#[must_use]
+pub fn switch_client(session_name: &str, options: &AttachOptions, socket: Option<&str>) -> bool {
+    if let Some(window) = &options.window {
+        if !select_window(session_name, window, socket) {
+            return false;
+        }
+    }
+    let mut cmd = command(socket);
+    cmd.arg("switch-client").arg("-t").arg(session_name);
+    if options.readonly {
+        cmd.arg("-r");
+    }
+    cmd.status()
         .expect("Failed to switch tmux client")
         .success()
 }
 
 /// Attaches to the specified tmux session.
 ///
+/// Honours `readonly` (`attach -r`), `detach_others` (`attach -d`) and jumps to
+/// [`AttachOptions::window`] first when requested.
+///
 /// # Panics
 /// Panics if the `tmux attach-session` command fails to execute.
 #[must_use]
-pub fn attach_session(session_name: &str) -> bool {
-    Command::new("tmux")
-        .arg("attach-session")
+pub fn attach_session(session_name: &str, options: &AttachOptions, socket: Option<&str>) -> bool {
+    if let Some(window) = &options.window {
+        if !select_window(session_name, window, socket) {
+            return false;
+        }
+    }
+    let mut cmd = command(socket);
+    cmd.arg("attach-session").arg("-t").arg(session_name);
+    if options.readonly {
+        cmd.arg("-r");
+    }
+    if options.detach_others {
+        cmd.arg("-d");
+    }
+    cmd.env_remove("TMUX")
+        .status()
+        .expect("Failed to attach to tmux session")
+        .success()
+}
+
+/// Kills the tmux session with the given name.
+///
+/// # Panics
+/// Panics if the `tmux kill-session` command fails to execute.
+#[must_use]
+pub fn kill_session(session_name: &str, socket: Option<&str>) -> bool {
+    command(socket)
+        .arg("kill-session")
         .arg("-t")
         .arg(session_name)
-        .env_remove("TMUX")
         .status()
-        .expect("Failed to attach to tmux session")
+        .expect("Failed to kill tmux session")
         .success()
 }
 
@@ -104,8 +219,8 @@ pub fn attach_session(session_name: &str) -> bool {
 /// # Panics
 /// Panics if the `tmux display-message` command fails to execute.
 #[must_use]
-pub fn get_current_session() -> Option<String> {
-    let output = Command::new("tmux")
+pub fn get_current_session(socket: Option<&str>) -> Option<String> {
+    let output = command(socket)
         .arg("display-message")
         .arg("-p")
         .arg("#S")